@@ -6,6 +6,7 @@ use std::{
     sync::PoisonError,
 };
 
+use ledb_types::KeyType;
 use lmdb::error::Error as DbError;
 use ron::Error as RonError;
 use serde_cbor::error::Error as CborError;
@@ -20,6 +21,72 @@ pub enum Error {
     StorageError(String),
     IoError(IoError),
     SyncError(String),
+    /// The requested document or collection does not exist
+    NotFound {
+        collection: String,
+        id: Option<u32>,
+    },
+    /// A unique index constraint was violated on insert/update
+    UniqueViolation {
+        collection: String,
+        index: String,
+    },
+    /// A value didn't match the declared key type of an index
+    IndexTypeMismatch {
+        index: String,
+        expected: KeyType,
+        found: KeyType,
+    },
+    /// A query could not be parsed
+    QueryParse(String),
+    /// A human-readable frame describing where the wrapped error happened
+    Context(String, Box<Error>),
+}
+
+impl Error {
+    /// Returns true if this error (or the cause it wraps, through any
+    /// [`Error::Context`] frames) represents a missing document or collection
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Error::NotFound { .. } => true,
+            Error::Context(_, cause) => cause.is_not_found(),
+            _ => false,
+        }
+    }
+
+    /// Returns true if this error (or the cause it wraps, through any
+    /// [`Error::Context`] frames) represents a unique index violation
+    pub fn is_unique_violation(&self) -> bool {
+        match self {
+            Error::UniqueViolation { .. } => true,
+            Error::Context(_, cause) => cause.is_unique_violation(),
+            _ => false,
+        }
+    }
+
+    /// Build a [`Error::NotFound`] for a primary-key lookup that missed
+    ///
+    /// `DbError::NotFound` is also returned by routine internal LMDB reads
+    /// (existence checks, get-or-default cursor reads) that should stay
+    /// `Ok(None)`, so this is only meaningful at the specific call site doing
+    /// the primary-key lookup, not as a blanket `From<DbError>` conversion.
+    pub fn not_found(collection: impl Into<String>, id: Option<u32>) -> Error {
+        Error::NotFound {
+            collection: collection.into(),
+            id,
+        }
+    }
+
+    /// Build a [`Error::UniqueViolation`] for a unique-index put that collided
+    ///
+    /// Meant to be constructed at the put call site, which knows which
+    /// collection and index raised `DbError::KeyExist`.
+    pub fn unique_violation(collection: impl Into<String>, index: impl Into<String>) -> Error {
+        Error::UniqueViolation {
+            collection: collection.into(),
+            index: index.into(),
+        }
+    }
 }
 
 impl Display for Error {
@@ -33,6 +100,34 @@ impl Display for Error {
             StorageError(s) => write!(f, "Storage error: {}", s),
             IoError(e) => write!(f, "I/O Error: {}", e),
             SyncError(s) => write!(f, "Sync error: {}", s),
+            NotFound {
+                collection,
+                id: Some(id),
+            } => {
+                write!(f, "Document not found: \"{}\"#{}", collection, id)
+            }
+            NotFound {
+                collection,
+                id: None,
+            } => {
+                write!(f, "Not found in \"{}\"", collection)
+            }
+            UniqueViolation { collection, index } => write!(
+                f,
+                "Unique index violation: \"{}\".\"{}\"",
+                collection, index
+            ),
+            IndexTypeMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Index type mismatch on \"{}\": expected {:?}, found {:?}",
+                index, expected, found
+            ),
+            QueryParse(s) => write!(f, "Query parse error: {}", s),
+            Context(msg, cause) => write!(f, "{}: {}", msg, cause),
         }
     }
 }
@@ -43,6 +138,140 @@ impl Into<String> for Error {
     }
 }
 
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use Error::*;
+        match self {
+            DbError(e) => Some(e),
+            DataError(e) => Some(e),
+            StrError(e) => Some(e),
+            IoError(e) => Some(e),
+            DocError(_)
+            | StorageError(_)
+            | SyncError(_)
+            | NotFound { .. }
+            | UniqueViolation { .. }
+            | IndexTypeMismatch { .. }
+            | QueryParse(_) => None,
+            Context(_, cause) => Some(cause.as_ref()),
+        }
+    }
+}
+
+impl Error {
+    /// Borrows the wrapped LMDB error, if this is (or wraps, through any
+    /// [`Error::Context`] frames) a [`Error::DbError`]
+    pub fn as_db_error(&self) -> Option<&DbError> {
+        match self {
+            Error::DbError(e) => Some(e),
+            Error::Context(_, cause) => cause.as_db_error(),
+            _ => None,
+        }
+    }
+
+    /// Borrows the wrapped CBOR coding error, if this is (or wraps, through
+    /// any [`Error::Context`] frames) a [`Error::DataError`]
+    pub fn as_cbor_error(&self) -> Option<&CborError> {
+        match self {
+            Error::DataError(e) => Some(e),
+            Error::Context(_, cause) => cause.as_cbor_error(),
+            _ => None,
+        }
+    }
+}
+
+/// Stable, machine-readable error codes, for e.g. an HTTP/RPC layer that
+/// needs to branch on the kind of failure without string-matching `Display`
+///
+/// Exhaustively mapped from [`Error`] in [`Error::code`] via a wildcard-free
+/// match, so adding a new `Error` variant without extending the mapping is a
+/// compile error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    Doc,
+    Db,
+    Str,
+    DataCoding,
+    Storage,
+    Io,
+    Sync,
+    NotFound,
+    UniqueViolation,
+    IndexTypeMismatch,
+    QueryParse,
+}
+
+impl ErrorCode {
+    /// The stable string form of this code, suitable for a JSON response
+    pub fn as_str(self) -> &'static str {
+        use ErrorCode::*;
+        match self {
+            Doc => "DOC",
+            Db => "DB",
+            Str => "STR",
+            DataCoding => "DATA_CODING",
+            Storage => "STORAGE",
+            Io => "IO",
+            Sync => "SYNC",
+            NotFound => "NOT_FOUND",
+            UniqueViolation => "UNIQUE_VIOLATION",
+            IndexTypeMismatch => "INDEX_TYPE_MISMATCH",
+            QueryParse => "QUERY_PARSE",
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Error {
+    /// The stable, machine-readable code for this error
+    ///
+    /// A [`Error::Context`] frame carries the code of the error it wraps.
+    pub fn code(&self) -> ErrorCode {
+        use Error::*;
+        match self {
+            DocError(_) => ErrorCode::Doc,
+            DbError(_) => ErrorCode::Db,
+            StrError(_) => ErrorCode::Str,
+            DataError(_) => ErrorCode::DataCoding,
+            StorageError(_) => ErrorCode::Storage,
+            IoError(_) => ErrorCode::Io,
+            SyncError(_) => ErrorCode::Sync,
+            NotFound { .. } => ErrorCode::NotFound,
+            UniqueViolation { .. } => ErrorCode::UniqueViolation,
+            IndexTypeMismatch { .. } => ErrorCode::IndexTypeMismatch,
+            QueryParse(_) => ErrorCode::QueryParse,
+            Context(_, cause) => cause.code(),
+        }
+    }
+}
+
+/// Serializes to `{ "code": ..., "message": ... }`, for returning a stable,
+/// parseable error response from a network-facing integration
+///
+/// Requires the `error-serde` feature, which must add an optional `serde`
+/// dependency in `Cargo.toml` (`serde = { version = "...", optional = true }`,
+/// `error-serde = ["serde"]`) so the core crate stays dependency-light by
+/// default.
+#[cfg(feature = "error-serde")]
+impl serde::Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Error", 2)?;
+        state.serialize_field("code", self.code().as_str())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
 /// Database result type
 pub type Result<T> = StdResult<T, Error>;
 
@@ -111,3 +340,38 @@ where
         self.map_err(Error::from)
     }
 }
+
+/// The helper for layering a human-readable frame onto an error, e.g. which
+/// collection, index or document was being processed when it happened
+pub trait ErrorContext<T> {
+    /// Annotate the error with a static or owned message
+    fn context<C>(self, msg: C) -> Result<T>
+    where
+        C: Into<String>;
+
+    /// Annotate the error with a lazily built message, skipped on the success path
+    fn with_context<C, F>(self, f: F) -> Result<T>
+    where
+        C: Into<String>,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> ErrorContext<T> for StdResult<T, E>
+where
+    Error: From<E>,
+{
+    fn context<C>(self, msg: C) -> Result<T>
+    where
+        C: Into<String>,
+    {
+        self.map_err(|e| Error::Context(msg.into(), Box::new(Error::from(e))))
+    }
+
+    fn with_context<C, F>(self, f: F) -> Result<T>
+    where
+        C: Into<String>,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|e| Error::Context(f().into(), Box::new(Error::from(e))))
+    }
+}